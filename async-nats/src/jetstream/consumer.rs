@@ -0,0 +1,254 @@
+// Copyright 2020-2022 The NATS Authors
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consume messages from JetStream streams.
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+
+use async_channel::Receiver;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use super::context::Context;
+use super::response::Response;
+use super::Message;
+use crate::Error;
+
+/// Information about a consumer, cached behind a [BroadcastStream].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Info {
+    /// The name of the stream the consumer belongs to.
+    pub stream_name: String,
+    /// The name of the consumer.
+    pub name: String,
+    /// The number of messages delivered but not yet acknowledged.
+    #[serde(default)]
+    pub num_ack_pending: usize,
+    /// The number of messages left to be delivered.
+    #[serde(default)]
+    pub num_pending: usize,
+}
+
+/// A JetStream consumer.
+#[derive(Debug, Clone)]
+pub struct Consumer {
+    pub(crate) context: Context,
+    pub(crate) stream_name: String,
+    pub(crate) name: String,
+    pub(crate) info: Info,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchRequest {
+    batch: usize,
+}
+
+impl Consumer {
+    /// Fetches the latest [Info] for this consumer from the server.
+    pub async fn fetch_info(&self) -> Result<Info, Error> {
+        let subject = format!("CONSUMER.INFO.{}.{}", self.stream_name, self.name);
+        match self.context.request(subject, &()).await? {
+            Response::Ok(info) => Ok(info),
+            Response::Err { error } => Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("failed to fetch consumer info: code {}", error.code),
+            ))),
+        }
+    }
+
+    /// Creates a ref-counted broadcaster over a single pull subscription,
+    /// returning a cloneable [BroadcastStream]. Cloning a handle shares the
+    /// underlying subscription and ack flow, so a pool of workers processing
+    /// the same durable consumer opens only one set of machinery instead of
+    /// one per worker.
+    ///
+    /// A single owner task pulls batches of `batch` messages and routes them
+    /// to whichever handle is ready to receive; every handle can still
+    /// `ack`/`ack_with` on the original reply subject. When the last handle is
+    /// dropped the owner task is torn down.
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), async_nats::Error> {
+    /// use futures::StreamExt;
+    /// let client = async_nats::connect("localhost:4222").await?;
+    /// let jetstream = async_nats::jetstream::new(client);
+    ///
+    /// let consumer = jetstream
+    ///     .get_stream("events").await?
+    ///     .get_consumer("workers").await?;
+    ///
+    /// let broadcast = consumer.broadcast(100).await?;
+    /// for _ in 0..4 {
+    ///     let mut handle = broadcast.clone();
+    ///     tokio::spawn(async move {
+    ///         while let Some(Ok(message)) = handle.next().await {
+    ///             message.ack().await.ok();
+    ///         }
+    ///     });
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn broadcast(&self, batch: usize) -> Result<BroadcastStream, Error> {
+        let (sender, receiver) = async_channel::bounded(batch.max(1));
+
+        let context = self.context.clone();
+        let next_subject = format!(
+            "{}.CONSUMER.MSG.NEXT.{}.{}",
+            context.prefix, self.stream_name, self.name
+        );
+        let inbox = context.client.new_inbox();
+        let mut subscription = context.client.subscribe(inbox.clone()).await?;
+
+        let handle = tokio::spawn(async move {
+            loop {
+                let request = match serde_json::to_vec(&BatchRequest { batch: batch.max(1) }) {
+                    Ok(request) => request,
+                    Err(_) => break,
+                };
+                if context
+                    .client
+                    .publish_with_reply(next_subject.clone(), inbox.clone(), request.into())
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+
+                // Only real messages count toward the batch; status frames
+                // (idle heartbeats, "No Messages", request timeouts, consumer
+                // deleted) share the inbox but carry no ack reply subject.
+                let mut received = 0;
+                while received < batch.max(1) {
+                    let message = match subscription.next().await {
+                        Some(message) => message,
+                        None => return,
+                    };
+
+                    match message.status {
+                        // Keep the pull alive without consuming from the batch.
+                        Some(crate::StatusCode::IDLE_HEARTBEAT) => continue,
+                        // The batch is drained; request a fresh one.
+                        Some(crate::StatusCode::NOT_FOUND)
+                        | Some(crate::StatusCode::REQUEST_TIMEOUT) => break,
+                        // Consumer deleted or any other terminal control frame.
+                        Some(_) => return,
+                        None => {
+                            received += 1;
+                            let message = Message {
+                                message,
+                                context: context.clone(),
+                            };
+                            // `Err` means every handle has been dropped.
+                            if sender.send(Ok(message)).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let shared = Arc::new(Shared {
+            info: Mutex::new(self.info.clone()),
+            consumer: self.clone(),
+            _owner: OwnerTask(handle),
+        });
+
+        Ok(BroadcastStream { shared, receiver })
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    info: Mutex<Info>,
+    consumer: Consumer,
+    _owner: OwnerTask,
+}
+
+/// Owns the pull-loop task and aborts it when the last handle is dropped.
+#[derive(Debug)]
+struct OwnerTask(tokio::task::JoinHandle<()>);
+
+impl Drop for OwnerTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// A cloneable handle onto a broadcast consumer subscription.
+///
+/// Handles compete for messages, forming a worker pool. Dropping the last
+/// handle tears down the underlying subscription.
+#[derive(Debug, Clone)]
+pub struct BroadcastStream {
+    shared: Arc<Shared>,
+    receiver: Receiver<Result<Message, Error>>,
+}
+
+impl BroadcastStream {
+    /// Returns the cached consumer [Info] shared across all handles without
+    /// round-tripping `$JS.API.CONSUMER.INFO`.
+    pub fn cached_info(&self) -> Info {
+        self.shared.info.lock().unwrap().clone()
+    }
+
+    /// Refreshes and returns the consumer [Info], updating the shared cache.
+    pub async fn info(&self) -> Result<Info, Error> {
+        let info = self.shared.consumer.fetch_info().await?;
+        *self.shared.info.lock().unwrap() = info.clone();
+        Ok(info)
+    }
+}
+
+impl Stream for BroadcastStream {
+    type Item = Result<Message, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OwnerTask;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn owner_task_aborts_when_dropped() {
+        // The task keeps a clone of `marker` alive for as long as it runs.
+        let marker = Arc::new(());
+        let task_marker = marker.clone();
+        let handle = tokio::spawn(async move {
+            let _held = task_marker;
+            std::future::pending::<()>().await;
+        });
+
+        let owner = OwnerTask(handle);
+        assert_eq!(Arc::strong_count(&marker), 2);
+
+        // Dropping the owner (as the last `BroadcastStream` handle would) must
+        // abort the task and release its clone.
+        drop(owner);
+        for _ in 0..100 {
+            if Arc::strong_count(&marker) == 1 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(Arc::strong_count(&marker), 1);
+    }
+}
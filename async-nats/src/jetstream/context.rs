@@ -0,0 +1,128 @@
+// Copyright 2020-2022 The NATS Authors
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Entrypoint to the JetStream API.
+
+use crate::{Client, Error, HeaderMap};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::publish::{PubAck, Publish, PublishError};
+use super::response::Response;
+
+/// A context which can perform JetStream scoped requests.
+#[derive(Clone, Debug)]
+pub struct Context {
+    pub(crate) client: Client,
+    pub(crate) prefix: String,
+}
+
+impl Context {
+    pub(crate) fn new(client: Client) -> Context {
+        Context {
+            client,
+            prefix: "$JS.API".to_string(),
+        }
+    }
+
+    pub(crate) fn with_prefix<T: ToString>(client: Client, prefix: T) -> Context {
+        Context {
+            client,
+            prefix: prefix.to_string(),
+        }
+    }
+
+    pub(crate) fn with_domain<T: AsRef<str>>(client: Client, domain: T) -> Context {
+        Context {
+            client,
+            prefix: format!("$JS.{}.API", domain.as_ref()),
+        }
+    }
+
+    /// Publishes a message to a stream and waits for the server
+    /// acknowledgement.
+    pub async fn publish(&self, subject: String, payload: Bytes) -> Result<PubAck, Error> {
+        let message = self.client.request(subject, payload).await?;
+        parse_pub_ack(message.payload.as_ref())
+    }
+
+    /// Publishes a message with headers to a stream and waits for the server
+    /// acknowledgement. Set the `Nats-Msg-Id` header to enable duplicate
+    /// detection.
+    pub async fn publish_with_headers(
+        &self,
+        subject: String,
+        headers: HeaderMap,
+        payload: Bytes,
+    ) -> Result<PubAck, Error> {
+        let message = self
+            .client
+            .request_with_headers(subject, headers, payload)
+            .await?;
+        parse_pub_ack(message.payload.as_ref())
+    }
+
+    /// Sends a [Publish] request, returning a typed [PublishError] when the
+    /// server rejects it — for example when an expected-stream or
+    /// expected-last-sequence assertion does not hold.
+    pub async fn send_publish(
+        &self,
+        subject: String,
+        publish: Publish,
+    ) -> Result<PubAck, PublishError> {
+        let Publish { payload, headers } = publish;
+        let message = match headers {
+            Some(headers) => self.client.request_with_headers(subject, headers, payload).await,
+            None => self.client.request(subject, payload).await,
+        }?;
+
+        match serde_json::from_slice(message.payload.as_ref())
+            .map_err(|err| PublishError::Request(Box::new(err)))?
+        {
+            Response::Ok(pub_ack) => Ok(pub_ack),
+            Response::Err { error } => Err(PublishError::Server(error)),
+        }
+    }
+
+    /// Sends a request to the JetStream API and parses the untagged
+    /// [Response] into either the expected payload or an API error.
+    pub(crate) async fn request<S, T, V>(&self, subject: S, payload: &T) -> Result<Response<V>, Error>
+    where
+        S: ToString,
+        T: ?Sized + Serialize,
+        V: DeserializeOwned,
+    {
+        let request = serde_json::to_vec(payload).map(Bytes::from)?;
+        let subject = format!("{}.{}", self.prefix, subject.to_string());
+
+        let message = self.client.request(subject, request).await?;
+        let response = serde_json::from_slice(message.payload.as_ref())?;
+
+        Ok(response)
+    }
+}
+
+fn parse_pub_ack(payload: &[u8]) -> Result<PubAck, Error> {
+    match serde_json::from_slice(payload)? {
+        Response::Ok(pub_ack) => Ok(pub_ack),
+        Response::Err { error } => Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "publish rejected: {} (code {})",
+                error.description.as_deref().unwrap_or("unknown"),
+                error.code
+            ),
+        ))),
+    }
+}
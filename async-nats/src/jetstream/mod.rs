@@ -84,12 +84,15 @@
 //! ```
 
 use crate::{Client, Error};
+use time::OffsetDateTime;
 
 pub mod consumer;
 pub mod context;
 pub mod publish;
 pub mod response;
 pub mod stream;
+#[cfg(feature = "typed")]
+pub mod typed;
 
 use bytes::Bytes;
 pub use context::Context;
@@ -302,7 +305,121 @@ impl Message {
             )))
         }
     }
+
+    /// Returns the [Info] describing the delivery of this message, parsed from
+    /// the ack reply subject.
+    ///
+    /// The reply subject encodes the stream and consumer the message was
+    /// delivered from, the redelivery count and the stream and consumer
+    /// sequence numbers, letting consumers implement dedup and
+    /// redelivery-aware logic without re-fetching stream state.
+    ///
+    /// Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), async_nats::Error> {
+    /// use futures::StreamExt;
+    /// let client = async_nats::connect("localhost:4222").await?;
+    /// let jetstream = async_nats::jetstream::new(client);
+    ///
+    /// let consumer = jetstream
+    ///     .get_stream("events").await?
+    ///     .get_consumer("pull").await?;
+    ///
+    /// let mut messages = consumer.fetch(100).await?;
+    ///
+    /// while let Some(message) = messages.next().await {
+    ///     let message = message?;
+    ///     println!("pending messages: {}", message.info()?.pending);
+    ///     message.ack().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn info(&self) -> Result<Info<'_>, Error> {
+        let reply = self.reply.as_ref().ok_or_else(|| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "No reply subject, not a JetStream message",
+            ))
+        })?;
+
+        parse_ack_subject(reply)
+    }
 }
+
+/// Parses the delivery [Info] out of a JetStream ack reply subject.
+fn parse_ack_subject(reply: &str) -> Result<Info<'_>, Error> {
+    const PREFIX: &str = "$JS.ACK.";
+
+    if !reply.starts_with(PREFIX) {
+        return Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "reply subject is not a JetStream ack subject",
+        )));
+    }
+
+    let tokens: Vec<&str> = reply.split('.').collect();
+
+    // The legacy layout has 9 tokens, while the newer layout inserts a
+    // `<domain>` and `<account_hash>` after `ACK` and appends a trailing
+    // random token, giving 12. Detect by token count and index from there.
+    let base = match tokens.len() {
+        9 => 2,
+        12 => 4,
+        _ => {
+            return Err(Box::new(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "unrecognized JetStream ack subject layout",
+            )))
+        }
+    };
+
+    let parse = |token: &str| -> Result<u64, Error> {
+        token.parse().map_err(|_| {
+            Box::new(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "failed to parse token in JetStream ack subject",
+            )) as Error
+        })
+    };
+
+    let timestamp = OffsetDateTime::from_unix_timestamp_nanos(parse(tokens[base + 5])? as i128)
+        .map_err(|err| {
+            Box::new(std::io::Error::new(std::io::ErrorKind::Other, err.to_string())) as Error
+        })?;
+
+    Ok(Info {
+        stream: tokens[base],
+        consumer: tokens[base + 1],
+        delivered: parse(tokens[base + 2])?,
+        stream_sequence: parse(tokens[base + 3])?,
+        consumer_sequence: parse(tokens[base + 4])?,
+        timestamp,
+        pending: parse(tokens[base + 6])?,
+    })
+}
+
+/// Information about a message delivery, parsed from the ack reply subject.
+#[derive(Debug, Clone)]
+pub struct Info<'a> {
+    /// The stream the message was delivered from.
+    pub stream: &'a str,
+    /// The consumer the message was delivered from.
+    pub consumer: &'a str,
+    /// The number of times this message was delivered (redelivery count).
+    pub delivered: u64,
+    /// The sequence number of the message in the stream.
+    pub stream_sequence: u64,
+    /// The sequence number of the message in the consumer.
+    pub consumer_sequence: u64,
+    /// The time the message was delivered.
+    pub timestamp: OffsetDateTime,
+    /// The number of messages known to be pending on the consumer.
+    pub pending: u64,
+}
+
 /// The kinds of response used for acknowledging a processed message.
 #[derive(Debug, Clone, Copy)]
 pub enum AckKind {
@@ -337,3 +454,45 @@ impl From<AckKind> for Bytes {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_ack_subject;
+
+    #[test]
+    fn parse_ack_subject_legacy_layout() {
+        let info =
+            parse_ack_subject("$JS.ACK.events.processor.2.10.7.1609459200000000000.3").unwrap();
+        assert_eq!(info.stream, "events");
+        assert_eq!(info.consumer, "processor");
+        assert_eq!(info.delivered, 2);
+        assert_eq!(info.stream_sequence, 10);
+        assert_eq!(info.consumer_sequence, 7);
+        assert_eq!(info.pending, 3);
+        assert_eq!(info.timestamp.unix_timestamp(), 1609459200);
+    }
+
+    #[test]
+    fn parse_ack_subject_new_layout() {
+        let info = parse_ack_subject(
+            "$JS.ACK.hub.ACCHASH.events.processor.2.10.7.1609459200000000000.3.RANDOM",
+        )
+        .unwrap();
+        assert_eq!(info.stream, "events");
+        assert_eq!(info.consumer, "processor");
+        assert_eq!(info.delivered, 2);
+        assert_eq!(info.stream_sequence, 10);
+        assert_eq!(info.consumer_sequence, 7);
+        assert_eq!(info.pending, 3);
+    }
+
+    #[test]
+    fn parse_ack_subject_rejects_bad_input() {
+        // Not an ack subject.
+        assert!(parse_ack_subject("inbox.1234").is_err());
+        // Unrecognized token count (10).
+        assert!(parse_ack_subject("$JS.ACK.a.b.c.d.e.f.g.h").is_err());
+        // Non-numeric token where a number is expected.
+        assert!(parse_ack_subject("$JS.ACK.events.processor.x.10.7.0.3").is_err());
+    }
+}
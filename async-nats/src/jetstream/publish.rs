@@ -0,0 +1,152 @@
+// Copyright 2020-2022 The NATS Authors
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Publishing messages into JetStream streams.
+
+use std::fmt;
+
+use bytes::Bytes;
+use serde::Deserialize;
+
+use crate::HeaderMap;
+
+/// Acknowledgement returned by the server for a successful publish.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PubAck {
+    /// Name of the stream the message was stored in.
+    pub stream: String,
+    /// Sequence number the message was stored at.
+    #[serde(default, rename = "seq")]
+    pub sequence: u64,
+    /// JetStream domain the stream lives in, if any.
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// Set to `true` when the server detected this message as a duplicate
+    /// within the stream's duplicate window and did not store it again.
+    #[serde(default)]
+    pub duplicate: bool,
+}
+
+/// A publish request with optional headers driving
+/// deduplication and optimistic-concurrency checks.
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), async_nats::Error> {
+/// use async_nats::jetstream::publish::Publish;
+/// let client = async_nats::connect("localhost:4222").await?;
+/// let jetstream = async_nats::jetstream::new(client);
+///
+/// let ack = jetstream
+///     .send_publish(
+///         "events".to_string(),
+///         Publish::build().message_id("1").payload("data".into()),
+///     )
+///     .await?;
+/// assert!(!ack.duplicate);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Publish {
+    pub(crate) payload: Bytes,
+    pub(crate) headers: Option<HeaderMap>,
+}
+
+impl Publish {
+    /// Creates a new, empty [Publish].
+    pub fn build() -> Self {
+        Default::default()
+    }
+
+    /// Sets the message payload.
+    pub fn payload(mut self, payload: Bytes) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Sets the `Nats-Msg-Id` header used for duplicate detection. Re-sending
+    /// a message with the same id within the stream's duplicate window makes
+    /// the server report it as a [duplicate][PubAck::duplicate] and keep the
+    /// original sequence, so a publish can be retried safely on timeout.
+    pub fn message_id<T: AsRef<str>>(self, id: T) -> Self {
+        self.header(crate::header::NATS_MESSAGE_ID, id.as_ref())
+    }
+
+    /// Asserts that the message is published to the given stream, failing the
+    /// publish with a typed error otherwise.
+    pub fn expected_stream<T: AsRef<str>>(self, stream: T) -> Self {
+        self.header(crate::header::NATS_EXPECTED_STREAM, stream.as_ref())
+    }
+
+    /// Asserts the sequence of the last message stored in the stream, enabling
+    /// optimistic-concurrency publishes.
+    pub fn expected_last_sequence(self, last_sequence: u64) -> Self {
+        self.header(
+            crate::header::NATS_EXPECTED_LAST_SEQUENCE,
+            last_sequence.to_string().as_str(),
+        )
+    }
+
+    /// Asserts the `Nats-Msg-Id` of the last message stored in the stream.
+    pub fn expected_last_message_id<T: AsRef<str>>(self, last_message_id: T) -> Self {
+        self.header(
+            crate::header::NATS_EXPECTED_LAST_MESSAGE_ID,
+            last_message_id.as_ref(),
+        )
+    }
+
+    /// Adds an arbitrary header to the publish.
+    pub fn header<K: crate::header::IntoHeaderName, V: AsRef<str>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        self.headers
+            .get_or_insert_with(HeaderMap::new)
+            .insert(key, value.as_ref());
+        self
+    }
+}
+
+/// Errors that can occur while publishing to a stream.
+#[derive(Debug)]
+pub enum PublishError {
+    /// The server rejected the publish, e.g. because an expected-stream or
+    /// expected-last-sequence assertion did not hold.
+    Server(super::response::Error),
+    /// The underlying request failed.
+    Request(crate::Error),
+}
+
+impl fmt::Display for PublishError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublishError::Server(err) => write!(
+                f,
+                "publish rejected by server: {} (code {})",
+                err.description.as_deref().unwrap_or("unknown"),
+                err.code
+            ),
+            PublishError::Request(err) => write!(f, "publish request failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PublishError {}
+
+impl From<crate::Error> for PublishError {
+    fn from(err: crate::Error) -> Self {
+        PublishError::Request(err)
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2020-2022 The NATS Authors
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Deserialize;
+
+/// Error returned by the JetStream API.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// HTTP like error code in the 300 to 500 range.
+    pub code: usize,
+    /// A human friendly description of the error.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The NATS error code unique to each kind of error.
+    #[serde(default, rename = "err_code")]
+    pub error_code: usize,
+}
+
+/// `Response` wraps a JetStream API reply which is either the expected
+/// payload or an [Error] returned by the server.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum Response<T> {
+    Err { error: Error },
+    Ok(T),
+}
@@ -0,0 +1,203 @@
+// Copyright 2020-2022 The NATS Authors
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Manage and use JetStream [Stream]s.
+
+use std::fmt;
+
+use bytes::Bytes;
+use serde::Serialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::context::Context;
+
+/// Handle to a JetStream [Stream].
+#[derive(Clone, Debug)]
+pub struct Stream {
+    pub(crate) name: String,
+    pub(crate) context: Context,
+}
+
+/// A message read directly from a stream via the Direct Get API.
+///
+/// Unlike ordinary request/reply JSON responses, a Direct Get reply is a
+/// *raw* message: the payload is the stored message body while the stream
+/// metadata is carried in the `Nats-Stream`, `Nats-Sequence`, `Nats-Subject`
+/// and `Nats-Time-Stamp` headers.
+#[derive(Debug, Clone)]
+pub struct DirectGetMessage {
+    /// Subject the stored message was published on.
+    pub subject: String,
+    /// Sequence number of the message in the stream.
+    pub sequence: u64,
+    /// Name of the stream the message was stored in.
+    pub stream: String,
+    /// Time the message was stored, parsed from the RFC3339 `Nats-Time-Stamp`.
+    pub timestamp: OffsetDateTime,
+    /// The stored message body.
+    pub payload: Bytes,
+    /// Headers stored alongside the message, if any.
+    pub headers: Option<crate::HeaderMap>,
+}
+
+/// Errors that can occur while performing a Direct Get.
+#[derive(Debug)]
+pub enum DirectGetError {
+    /// No message matched the request.
+    NotFound,
+    /// The request could not be serialized.
+    Serialize(serde_json::Error),
+    /// A required metadata header was missing or malformed.
+    InvalidResponse(String),
+    /// The underlying request failed.
+    Other(crate::Error),
+}
+
+impl fmt::Display for DirectGetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DirectGetError::NotFound => write!(f, "no message found"),
+            DirectGetError::Serialize(err) => write!(f, "failed to serialize request: {}", err),
+            DirectGetError::InvalidResponse(header) => {
+                write!(f, "invalid direct get response: {}", header)
+            }
+            DirectGetError::Other(err) => write!(f, "direct get request failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DirectGetError {}
+
+impl From<crate::Error> for DirectGetError {
+    fn from(err: crate::Error) -> Self {
+        DirectGetError::Other(err)
+    }
+}
+
+impl From<serde_json::Error> for DirectGetError {
+    fn from(err: serde_json::Error) -> Self {
+        DirectGetError::Serialize(err)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DirectGetRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_by_subj: Option<String>,
+}
+
+impl Stream {
+    /// Gets the first message from the stream that is stored on `subject` with
+    /// a sequence equal or greater than `sequence`.
+    ///
+    /// The stream must have been created with `allow_direct: true`; the read
+    /// can be served by any replica rather than only the leader.
+    pub async fn direct_get_next_for_subject<S: ToString>(
+        &self,
+        subject: S,
+        sequence: u64,
+    ) -> Result<DirectGetMessage, DirectGetError> {
+        let request = DirectGetRequest {
+            seq: Some(sequence),
+            next_by_subj: Some(subject.to_string()),
+        };
+        let payload = serde_json::to_vec(&request).map(Bytes::from)?;
+        self.direct_get_request(format!("DIRECT.GET.{}", self.name), payload)
+            .await
+    }
+
+    /// Gets the last message from the stream that is stored on `subject`.
+    pub async fn direct_get_last_for_subject<S: AsRef<str>>(
+        &self,
+        subject: S,
+    ) -> Result<DirectGetMessage, DirectGetError> {
+        // The last-by-subject endpoint encodes the subject in the request
+        // subject itself and expects an empty body.
+        self.direct_get_request(
+            format!("DIRECT.GET.{}.{}", self.name, subject.as_ref()),
+            Bytes::new(),
+        )
+        .await
+    }
+
+    /// Gets a message from the stream by its `sequence` number.
+    pub async fn direct_get(
+        &self,
+        sequence: u64,
+    ) -> Result<DirectGetMessage, DirectGetError> {
+        let request = DirectGetRequest {
+            seq: Some(sequence),
+            next_by_subj: None,
+        };
+        let payload = serde_json::to_vec(&request).map(Bytes::from)?;
+        self.direct_get_request(format!("DIRECT.GET.{}", self.name), payload)
+            .await
+    }
+
+    async fn direct_get_request(
+        &self,
+        subject: String,
+        request: Bytes,
+    ) -> Result<DirectGetMessage, DirectGetError> {
+        let subject = format!("{}.{}", self.context.prefix, subject);
+
+        let message = self.context.client.request(subject, request).await?;
+
+        DirectGetMessage::from_message(message)
+    }
+}
+
+impl DirectGetMessage {
+    fn from_message(message: crate::Message) -> Result<DirectGetMessage, DirectGetError> {
+        let headers = match message.headers {
+            Some(headers) => headers,
+            None => return Err(DirectGetError::NotFound),
+        };
+
+        // A 404 is surfaced as a status message carrying a `Status` header and
+        // no stream metadata; treat it as a missing message.
+        if headers.get("Status").map(|s| s.as_str()) == Some("404") {
+            return Err(DirectGetError::NotFound);
+        }
+
+        let subject = header(&headers, "Nats-Subject")?.to_string();
+        let stream = header(&headers, "Nats-Stream")?.to_string();
+        let sequence = header(&headers, "Nats-Sequence")?
+            .parse()
+            .map_err(|_| DirectGetError::InvalidResponse("Nats-Sequence".to_string()))?;
+        let timestamp = OffsetDateTime::parse(header(&headers, "Nats-Time-Stamp")?, &Rfc3339)
+            .map_err(|_| DirectGetError::InvalidResponse("Nats-Time-Stamp".to_string()))?;
+
+        Ok(DirectGetMessage {
+            subject,
+            sequence,
+            stream,
+            timestamp,
+            payload: message.payload,
+            headers: Some(headers),
+        })
+    }
+}
+
+fn header<'a>(
+    headers: &'a crate::HeaderMap,
+    name: &str,
+) -> Result<&'a str, DirectGetError> {
+    headers
+        .get(name)
+        .map(|value| value.as_str())
+        .ok_or_else(|| DirectGetError::InvalidResponse(name.to_string()))
+}
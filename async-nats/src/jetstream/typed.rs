@@ -0,0 +1,258 @@
+// Copyright 2020-2022 The NATS Authors
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A typed, schema-aware layer over [Context] and [Message].
+//!
+//! This layer lets callers bind a serde-(de)serializable message type to a
+//! subject — mirroring the channel/message contracts found in AsyncAPI specs —
+//! and publish or consume it without hand-writing (de)serialization for every
+//! consumer. An optional per-subject JSON-Schema hook rejects malformed
+//! payloads both at publish and on receive.
+//!
+//! The layer is gated behind the `typed` feature.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::context::Context;
+use super::publish::PubAck;
+use super::Message;
+use crate::Error;
+
+/// The header used to carry the payload codec's media type.
+pub const CONTENT_TYPE: &str = "content-type";
+
+/// A codec used to (de)serialize typed payloads.
+///
+/// A [JsonCodec] is provided; implement this trait to plug in another format.
+pub trait Codec: Send + Sync {
+    /// The media type advertised in the [CONTENT_TYPE] header.
+    fn content_type(&self) -> &'static str;
+    /// Encodes a value into a payload.
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Bytes, SchemaError>;
+    /// Decodes a payload into a value.
+    fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T, SchemaError>;
+}
+
+/// The default JSON codec.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Bytes, SchemaError> {
+        serde_json::to_vec(value)
+            .map(Bytes::from)
+            .map_err(|err| SchemaError::Codec(Box::new(err)))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, payload: &[u8]) -> Result<T, SchemaError> {
+        serde_json::from_slice(payload).map_err(|err| SchemaError::Codec(Box::new(err)))
+    }
+}
+
+/// An error raised while encoding, decoding or validating a typed payload.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The payload could not be (de)serialized.
+    Codec(Error),
+    /// The payload did not satisfy the registered schema for its subject.
+    Validation(String),
+    /// The publish could not be delivered, or was rejected by the server.
+    Publish(Error),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Codec(err) => write!(f, "payload codec error: {}", err),
+            SchemaError::Validation(reason) => write!(f, "schema validation failed: {}", reason),
+            SchemaError::Publish(err) => write!(f, "publish failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// A registry of JSON schemas keyed by subject.
+///
+/// When the registry is passed to [Context::publish_typed_with] or
+/// [TypedMessage::payload_with] and a schema is registered for the subject,
+/// the payload is validated against it, and a [SchemaError::Validation] is
+/// returned when it does not conform. [Context::publish_typed] and
+/// [TypedMessage::payload] take no registry and do not validate.
+#[derive(Clone, Default)]
+pub struct SchemaRegistry {
+    schemas: Arc<HashMap<String, jsonschema::JSONSchema>>,
+}
+
+impl fmt::Debug for SchemaRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaRegistry")
+            .field("subjects", &self.schemas.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SchemaRegistry {
+    /// Builds a registry from an iterator of `(subject, schema)` pairs.
+    pub fn new<I, S>(schemas: I) -> Result<SchemaRegistry, SchemaError>
+    where
+        I: IntoIterator<Item = (S, serde_json::Value)>,
+        S: Into<String>,
+    {
+        let mut compiled = HashMap::new();
+        for (subject, schema) in schemas {
+            let schema = jsonschema::JSONSchema::compile(&schema)
+                .map_err(|err| SchemaError::Validation(err.to_string()))?;
+            compiled.insert(subject.into(), schema);
+        }
+        Ok(SchemaRegistry {
+            schemas: Arc::new(compiled),
+        })
+    }
+
+    /// Validates a JSON value against the schema registered for `subject`.
+    ///
+    /// Subjects with no registered schema are accepted unconditionally.
+    pub fn validate(&self, subject: &str, value: &serde_json::Value) -> Result<(), SchemaError> {
+        if let Some(schema) = self.schemas.get(subject) {
+            if let Err(errors) = schema.validate(value) {
+                let reason = errors.map(|err| err.to_string()).collect::<Vec<_>>().join("; ");
+                return Err(SchemaError::Validation(reason));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Context {
+    /// Publishes a typed payload, serializing it with the default [JsonCodec]
+    /// and setting the [CONTENT_TYPE] header.
+    pub async fn publish_typed<T: Serialize>(
+        &self,
+        subject: String,
+        payload: &T,
+    ) -> Result<PubAck, SchemaError> {
+        self.publish_typed_with(subject, payload, &JsonCodec, None).await
+    }
+
+    /// Publishes a typed payload with an explicit codec and an optional schema
+    /// registry. When a registry is supplied, the payload is validated against
+    /// the schema registered for `subject` before being sent.
+    pub async fn publish_typed_with<T, C>(
+        &self,
+        subject: String,
+        payload: &T,
+        codec: &C,
+        registry: Option<&SchemaRegistry>,
+    ) -> Result<PubAck, SchemaError>
+    where
+        T: Serialize,
+        C: Codec,
+    {
+        if let Some(registry) = registry {
+            let value = serde_json::to_value(payload)
+                .map_err(|err| SchemaError::Codec(Box::new(err)))?;
+            registry.validate(&subject, &value)?;
+        }
+
+        let payload = codec.encode(payload)?;
+        let mut headers = crate::HeaderMap::new();
+        headers.insert(CONTENT_TYPE, codec.content_type());
+
+        self.publish_with_headers(subject, headers, payload)
+            .await
+            .map_err(SchemaError::Publish)
+    }
+}
+
+/// A typed wrapper over a JetStream [Message] that lazily deserializes its
+/// payload into `T`.
+///
+/// The underlying [Message] is still reachable through [Deref][std::ops::Deref],
+/// so `ack`/`ack_with`/`double_ack` remain available.
+#[derive(Debug)]
+pub struct TypedMessage<T> {
+    message: Message,
+    _marker: PhantomData<T>,
+}
+
+impl<T> TypedMessage<T>
+where
+    T: DeserializeOwned,
+{
+    /// Wraps a [Message] in a typed view.
+    pub fn new(message: Message) -> TypedMessage<T> {
+        TypedMessage {
+            message,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Deserializes the payload with the default [JsonCodec].
+    pub fn payload(&self) -> Result<T, SchemaError> {
+        JsonCodec.decode(self.message.payload.as_ref())
+    }
+
+    /// Deserializes the payload with `codec`, validating it against the schema
+    /// registered for the message's subject when a registry is supplied.
+    pub fn payload_with<C: Codec>(
+        &self,
+        codec: &C,
+        registry: Option<&SchemaRegistry>,
+    ) -> Result<T, SchemaError> {
+        if let Some(registry) = registry {
+            let value: serde_json::Value = serde_json::from_slice(self.message.payload.as_ref())
+                .map_err(|err| SchemaError::Codec(Box::new(err)))?;
+            registry.validate(&self.message.subject, &value)?;
+        }
+        codec.decode(self.message.payload.as_ref())
+    }
+
+    /// Returns a reference to the underlying [Message].
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Consumes the wrapper, returning the underlying [Message].
+    pub fn into_inner(self) -> Message {
+        self.message
+    }
+}
+
+impl<T> std::ops::Deref for TypedMessage<T> {
+    type Target = Message;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+impl<T> From<Message> for TypedMessage<T>
+where
+    T: DeserializeOwned,
+{
+    fn from(message: Message) -> TypedMessage<T> {
+        TypedMessage::new(message)
+    }
+}